@@ -27,8 +27,6 @@ fn main() {
     for _ in 1..10 {
         thread::sleep(Duration::from_secs(1));
 
-        device.send(X, 50).unwrap();
-        device.send(Y, 50).unwrap();
-        device.synchronize().unwrap();
+        device.emit(&[(X, 50), (Y, 50)]).unwrap();
     }
 }
@@ -1,7 +1,12 @@
 use ffi::*;
 use libc::c_int;
 use nix::{self, errno::Errno, fcntl, sys::stat, unistd};
-use std::{ffi::CString, mem, path::Path, slice};
+use std::{
+    ffi::CString,
+    mem,
+    path::{Path, PathBuf},
+    slice,
+};
 
 #[cfg(feature = "udev")]
 use udev;
@@ -14,15 +19,23 @@ use crate::{
 
 /// Device builder.
 pub struct Builder {
+    path: PathBuf,
     fd: c_int,
     def: uinput_user_dev,
     abs: Option<c_int>,
+    abs_enabled: Vec<c_int>,
+    absres: [i32; ABS_CNT as usize],
+    modern: bool,
+    key_bits: Vec<(c_int, c_int)>,
+    rel_bits: Vec<(c_int, c_int)>,
+    ff: bool,
 }
 
 impl Builder {
     /// Create a builder from the specified path.
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
         Ok(Builder {
+            path: path.as_ref().to_path_buf(),
             fd: fcntl::open(
                 path.as_ref(),
                 fcntl::OFlag::O_WRONLY | fcntl::OFlag::O_NONBLOCK,
@@ -30,6 +43,12 @@ impl Builder {
             )?,
             def: unsafe { mem::zeroed() },
             abs: None,
+            abs_enabled: Vec::new(),
+            absres: [0; ABS_CNT as usize],
+            modern: false,
+            key_bits: Vec::new(),
+            rel_bits: Vec::new(),
+            ff: false,
         })
     }
 
@@ -156,6 +175,8 @@ impl Builder {
                         Errno::result(ui_set_keybit(self.fd, value.code()))?;
                     }
 
+                    self.key_bits.push((value.kind(), value.code()));
+
                     Ok(self)
                 }
             },
@@ -205,6 +226,8 @@ impl Builder {
                         Errno::result(ui_set_keybit(self.fd, value.code()))?;
                     }
 
+                    self.key_bits.push((value.kind(), value.code()));
+
                     Ok(self)
                 }
             },
@@ -215,6 +238,8 @@ impl Builder {
                     Errno::result(ui_set_relbit(self.fd, value.code()))?;
                 }
 
+                self.rel_bits.push((value.kind(), value.code()));
+
                 Ok(self)
             }
 
@@ -226,11 +251,83 @@ impl Builder {
 
                 self.abs = Some(value.code());
 
+                if !self.abs_enabled.contains(&value.code()) {
+                    self.abs_enabled.push(value.code());
+                }
+
                 Ok(self)
             }
         }
     }
 
+    /// Set the maximum number of force-feedback effects the device can have
+    /// uploaded at once, and enable the `EV_FF` event type (along with
+    /// `FF_RUMBLE`/`FF_PERIODIC`, the effect kinds `Device::poll_ff` knows
+    /// how to decode) so the kernel will forward effect-management events.
+    ///
+    /// Actually applying the `EV_FF`/`FF_*` bits, and reopening the device
+    /// fd read-write so `Device::poll_ff` can read them back, is deferred to
+    /// `create`.
+    pub fn ff_effects_max(mut self, value: u32) -> Result<Self, Box<dyn std::error::Error>> {
+        self.def.ff_effects_max = value;
+        self.ff = true;
+
+        Ok(self)
+    }
+
+    /// Enable the multitouch (MT type-B) axes — `ABS_MT_SLOT`,
+    /// `ABS_MT_TRACKING_ID`, `ABS_MT_POSITION_X` and `ABS_MT_POSITION_Y` —
+    /// so the device can be driven with `touch::MultiTouch`.
+    ///
+    /// `slots` is the maximum number of simultaneous contacts, and
+    /// `x_max`/`y_max` are the largest X/Y coordinates of the touch surface
+    /// (the minimum is 0).
+    pub fn multi_touch(
+        mut self,
+        slots: i32,
+        x_max: i32,
+        y_max: i32,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        unsafe {
+            Errno::result(ui_set_evbit(self.fd, EV_ABS as c_int))?;
+
+            for code in [
+                ABS_MT_SLOT,
+                ABS_MT_TRACKING_ID,
+                ABS_MT_POSITION_X,
+                ABS_MT_POSITION_Y,
+            ] {
+                Errno::result(ui_set_absbit(self.fd, code as c_int))?;
+            }
+        }
+
+        self.set_abs_range(ABS_MT_SLOT as c_int, 0, slots - 1);
+        self.set_abs_range(ABS_MT_TRACKING_ID as c_int, 0, i32::MAX);
+        self.set_abs_range(ABS_MT_POSITION_X as c_int, 0, x_max);
+        self.set_abs_range(ABS_MT_POSITION_Y as c_int, 0, y_max);
+
+        Ok(self)
+    }
+
+    fn set_abs_range(&mut self, code: c_int, min: i32, max: i32) {
+        self.def.absmin[code as usize] = min;
+        self.def.absmax[code as usize] = max;
+
+        if !self.abs_enabled.contains(&code) {
+            self.abs_enabled.push(code);
+        }
+    }
+
+    /// Use the modern `UI_DEV_SETUP`/`UI_ABS_SETUP` ioctls instead of writing
+    /// the legacy `uinput_user_dev` struct.
+    ///
+    /// This is required if any axis needs a `resolution`, which the legacy
+    /// struct has no room for.
+    pub fn modern(mut self) -> Self {
+        self.modern = true;
+        self
+    }
+
     /// Set the maximum value for the previously enabled absolute event.
     pub fn max(mut self, value: i32) -> Self {
         self.def.absmax[self.abs.unwrap() as usize] = value;
@@ -255,13 +352,92 @@ impl Builder {
         self
     }
 
+    /// Set the resolution value for the previously enabled absolute event.
+    ///
+    /// Only takes effect when built with [`Builder::modern`], since the
+    /// legacy `uinput_user_dev` struct can't express a resolution.
+    pub fn resolution(mut self, value: i32) -> Self {
+        self.absres[self.abs.unwrap() as usize] = value;
+        self
+    }
+
+    /// Reopen the device fd read-write (rather than write-only) so that
+    /// `Device::poll_ff` can read effect-management events back from the
+    /// kernel, then replay every bit enabled so far on the new fd along
+    /// with the `EV_FF`/`FF_*` bits force-feedback needs.
+    fn reopen_rw_for_ff(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        unistd::close(self.fd).ok();
+
+        self.fd = fcntl::open(
+            &self.path,
+            fcntl::OFlag::O_RDWR | fcntl::OFlag::O_NONBLOCK,
+            stat::Mode::empty(),
+        )?;
+
+        unsafe {
+            for &(evbit, code) in &self.key_bits {
+                Errno::result(ui_set_evbit(self.fd, evbit))?;
+                Errno::result(ui_set_keybit(self.fd, code))?;
+            }
+
+            for &(evbit, code) in &self.rel_bits {
+                Errno::result(ui_set_evbit(self.fd, evbit))?;
+                Errno::result(ui_set_relbit(self.fd, code))?;
+            }
+
+            for &code in &self.abs_enabled {
+                Errno::result(ui_set_evbit(self.fd, EV_ABS as c_int))?;
+                Errno::result(ui_set_absbit(self.fd, code))?;
+            }
+
+            Errno::result(ui_set_evbit(self.fd, EV_FF as c_int))?;
+            Errno::result(ui_set_ffbit(self.fd, FF_RUMBLE as c_int))?;
+            Errno::result(ui_set_ffbit(self.fd, FF_PERIODIC as c_int))?;
+        }
+
+        Ok(())
+    }
+
     /// Create the defined device.
-    pub fn create(self) -> Result<Device, Box<dyn std::error::Error>> {
+    pub fn create(mut self) -> Result<Device, Box<dyn std::error::Error>> {
+        if self.ff {
+            self.reopen_rw_for_ff()?;
+        }
+
         unsafe {
-            let ptr = std::ptr::addr_of!(self.def).cast::<u8>();
-            let size = mem::size_of_val(&self.def);
+            if self.modern {
+                for code in &self.abs_enabled {
+                    let code = *code as usize;
+
+                    let setup = uinput_abs_setup {
+                        code: code as u16,
+                        absinfo: input_absinfo {
+                            value: 0,
+                            minimum: self.def.absmin[code],
+                            maximum: self.def.absmax[code],
+                            fuzz: self.def.absfuzz[code],
+                            flat: self.def.absflat[code],
+                            resolution: self.absres[code],
+                        },
+                    };
+
+                    Errno::result(ui_abs_setup(self.fd, &setup))?;
+                }
+
+                let setup = uinput_setup {
+                    id: self.def.id,
+                    name: self.def.name,
+                    ff_effects_max: self.def.ff_effects_max,
+                };
+
+                Errno::result(ui_dev_setup(self.fd, &setup))?;
+            } else {
+                let ptr = std::ptr::addr_of!(self.def).cast::<u8>();
+                let size = mem::size_of_val(&self.def);
+
+                unistd::write(self.fd, slice::from_raw_parts(ptr, size))?;
+            }
 
-            unistd::write(self.fd, slice::from_raw_parts(ptr, size))?;
             Errno::result(ui_dev_create(self.fd))?;
         }
 
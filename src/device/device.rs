@@ -1,17 +1,70 @@
 use crate::{
-    event::{Code, Kind, Position, Press, Release},
+    event::{keyboard::Key, Code, Kind, Position, Press, Release},
+    layout::{Layout, Us},
     Event,
 };
 use ffi::*;
 use libc::{c_int, gettimeofday, timeval};
-use nix::unistd;
-use std::{mem, ptr, slice};
+use nix::{errno::Errno, unistd};
+use std::{
+    io, mem,
+    path::{Path, PathBuf},
+    ptr, slice,
+};
+
+#[cfg(feature = "udev")]
+use udev;
+
+/// Large enough for any `inputN` sysfs name the kernel reports.
+const MAX_SYSNAME_SIZE: usize = 64;
+
+fn now() -> timeval {
+    let mut time = timeval {
+        tv_sec: 0,
+        tv_usec: 0,
+    };
+
+    unsafe {
+        gettimeofday(&mut time, ptr::null_mut());
+    }
+
+    time
+}
 
 /// The virtual device.
 pub struct Device {
     fd: c_int,
 }
 
+/// A force-feedback effect-management event the kernel is asking us to
+/// service, returned by `Device::poll_ff`.
+pub enum FfRequest {
+    /// Upload an effect so it can be played back later. `effect_id`
+    /// identifies the slot the kernel assigned it.
+    Upload {
+        effect_id: i16,
+        length: u16,
+        delay: u16,
+        data: FfEffectData,
+    },
+
+    /// Erase a previously uploaded effect.
+    Erase { effect_id: u32 },
+}
+
+/// The effect-specific payload of an `FfRequest::Upload`, decoded from the
+/// kernel's `union ff_effect_data` according to `ff_effect::kind`.
+pub enum FfEffectData {
+    Rumble {
+        strong_magnitude: u16,
+        weak_magnitude: u16,
+    },
+    Periodic {
+        magnitude: i16,
+        period: u16,
+    },
+}
+
 impl Device {
     /// Wrap a file descriptor in a `Device`.
     pub fn new(fd: c_int) -> Self {
@@ -26,18 +79,13 @@ impl Device {
         value: c_int,
     ) -> Result<(), Box<dyn std::error::Error>> {
         unsafe {
-            let mut event = input_event {
-                time: timeval {
-                    tv_sec: 0,
-                    tv_usec: 0,
-                },
+            let event = input_event {
+                time: now(),
                 kind: kind as u16,
                 code: code as u16,
                 value: value as i32,
             };
 
-            gettimeofday(&mut event.time, ptr::null_mut());
-
             let ptr = std::ptr::addr_of!(event).cast::<u8>();
             let size = mem::size_of_val(&event);
 
@@ -83,6 +131,91 @@ impl Device {
         Ok(())
     }
 
+    /// Write a batch of `(event, value)` pairs as a single atomic input
+    /// frame: all of the given events share one timestamp and are followed
+    /// by a single `SYN_REPORT`, so consumers never observe the group
+    /// half-applied.
+    pub fn emit<T: Into<Event> + Copy>(
+        &mut self,
+        events: &[(T, i32)],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let time = now();
+
+        let mut batch: Vec<input_event> = events
+            .iter()
+            .map(|&(event, value)| {
+                let event = event.into();
+
+                input_event {
+                    time,
+                    kind: event.kind() as u16,
+                    code: event.code() as u16,
+                    value,
+                }
+            })
+            .collect();
+
+        batch.push(input_event {
+            time,
+            kind: EV_SYN,
+            code: SYN_REPORT,
+            value: 0,
+        });
+
+        self.write_batch(&batch)
+    }
+
+    /// Press every event in `events`, then append a single `SYN_REPORT`.
+    pub fn press_all<T: Press>(&mut self, events: &[&T]) -> Result<(), Box<dyn std::error::Error>> {
+        self.write_batch_of(events, 1)
+    }
+
+    /// Release every event in `events`, then append a single `SYN_REPORT`.
+    pub fn release_all<T: Release>(
+        &mut self,
+        events: &[&T],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.write_batch_of(events, 0)
+    }
+
+    fn write_batch_of<T: Kind + Code>(
+        &mut self,
+        events: &[&T],
+        value: i32,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let time = now();
+
+        let mut batch: Vec<input_event> = events
+            .iter()
+            .map(|event| input_event {
+                time,
+                kind: event.kind() as u16,
+                code: event.code() as u16,
+                value,
+            })
+            .collect();
+
+        batch.push(input_event {
+            time,
+            kind: EV_SYN,
+            code: SYN_REPORT,
+            value: 0,
+        });
+
+        self.write_batch(&batch)
+    }
+
+    fn write_batch(&mut self, batch: &[input_event]) -> Result<(), Box<dyn std::error::Error>> {
+        unsafe {
+            let ptr = batch.as_ptr().cast::<u8>();
+            let size = mem::size_of_val(batch);
+
+            unistd::write(self.fd, slice::from_raw_parts(ptr, size))?;
+        }
+
+        Ok(())
+    }
+
     /// Send a relative or absolute positioning event.
     pub fn position<T: Position>(
         &mut self,
@@ -91,6 +224,206 @@ impl Device {
     ) -> Result<(), Box<dyn std::error::Error>> {
         self.write(event.kind(), event.code(), value)
     }
+
+    /// Poll for a pending force-feedback upload/erase request and service it.
+    ///
+    /// Returns `Ok(None)` if nothing is pending right now, since the device
+    /// fd is opened non-blocking. Requires a device built with
+    /// `Builder::ff_effects_max`.
+    pub fn poll_ff(&mut self) -> Result<Option<FfRequest>, Box<dyn std::error::Error>> {
+        let mut event: input_event = unsafe { mem::zeroed() };
+
+        let size = mem::size_of::<input_event>();
+        let buf = unsafe { slice::from_raw_parts_mut(&mut event as *mut _ as *mut u8, size) };
+
+        let read = match unistd::read(self.fd, buf) {
+            Ok(read) => read,
+            Err(Errno::EAGAIN) => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        if read != size || event.kind != EV_UINPUT {
+            return Ok(None);
+        }
+
+        match event.code {
+            UI_FF_UPLOAD => {
+                let mut upload: uinput_ff_upload = unsafe { mem::zeroed() };
+                upload.request_id = event.value as u32;
+
+                unsafe {
+                    Errno::result(ui_begin_ff_upload(self.fd, &mut upload))?;
+                }
+
+                let data = match upload.effect.kind {
+                    FF_RUMBLE => {
+                        let rumble = unsafe { upload.effect.u.rumble };
+
+                        FfEffectData::Rumble {
+                            strong_magnitude: rumble.strong_magnitude,
+                            weak_magnitude: rumble.weak_magnitude,
+                        }
+                    }
+
+                    FF_PERIODIC => {
+                        let periodic = unsafe { upload.effect.u.periodic };
+
+                        FfEffectData::Periodic {
+                            magnitude: periodic.magnitude,
+                            period: periodic.period,
+                        }
+                    }
+
+                    kind => {
+                        // Still finish the handshake so the kernel isn't
+                        // left waiting, just tell it the upload failed.
+                        upload.retval = -(Errno::EINVAL as i32);
+
+                        unsafe {
+                            ui_end_ff_upload(self.fd, &mut upload);
+                        }
+
+                        return Err(
+                            format!("unsupported force-feedback effect kind {}", kind).into()
+                        );
+                    }
+                };
+
+                let request = FfRequest::Upload {
+                    effect_id: upload.effect.id,
+                    length: upload.effect.replay.length,
+                    delay: upload.effect.replay.delay,
+                    data,
+                };
+
+                upload.retval = 0;
+
+                unsafe {
+                    Errno::result(ui_end_ff_upload(self.fd, &mut upload))?;
+                }
+
+                Ok(Some(request))
+            }
+
+            UI_FF_ERASE => {
+                let mut erase: uinput_ff_erase = unsafe { mem::zeroed() };
+                erase.request_id = event.value as u32;
+
+                unsafe {
+                    Errno::result(ui_begin_ff_erase(self.fd, &mut erase))?;
+                }
+
+                let request = FfRequest::Erase {
+                    effect_id: erase.effect_id,
+                };
+
+                erase.retval = 0;
+
+                unsafe {
+                    Errno::result(ui_end_ff_erase(self.fd, &mut erase))?;
+                }
+
+                Ok(Some(request))
+            }
+
+            _ => Ok(None),
+        }
+    }
+
+    /// Type out `s` using the built-in US QWERTY layout, synchronizing after
+    /// each character. See `type_str_as` to use a different `Layout`.
+    pub fn type_str(&mut self, s: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.type_str_as(s, &Us)
+    }
+
+    /// Type out `s` using the given `Layout`, synchronizing after each
+    /// character. Characters the layout has no mapping for return an error
+    /// rather than being silently dropped.
+    pub fn type_str_as<L: Layout>(
+        &mut self,
+        s: &str,
+        layout: &L,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        for ch in s.chars() {
+            let step = layout
+                .lookup(ch)
+                .ok_or_else(|| format!("no key mapping for character {:?}", ch))?;
+
+            if step.shift {
+                self.press(&Key::LeftShift)?;
+            }
+
+            self.click(&step.key)?;
+
+            if step.shift {
+                self.release(&Key::LeftShift)?;
+            }
+
+            self.synchronize()?;
+        }
+
+        Ok(())
+    }
+
+    /// The `inputN` sysfs name the kernel assigned this device.
+    pub fn sysname(&self) -> io::Result<String> {
+        let mut buf = [0u8; MAX_SYSNAME_SIZE];
+
+        unsafe {
+            Errno::result(ui_get_sysname(self.fd, &mut buf))
+                .map_err(|err| io::Error::from_raw_os_error(err as i32))?;
+        }
+
+        let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+
+        Ok(String::from_utf8_lossy(&buf[..len]).into_owned())
+    }
+
+    /// Resolve `sysname` to the `/dev/input/eventN` path for this device.
+    #[cfg(feature = "udev")]
+    pub fn devnode(&self) -> io::Result<PathBuf> {
+        let sysname = self.sysname()?;
+
+        let context = udev::Context::new().map_err(to_io_error)?;
+
+        let parent = udev::Device::from_subsystem_sysname(&context, "input".to_string(), sysname)
+            .map_err(to_io_error)?;
+
+        let mut enumerator = udev::Enumerator::new(&context).map_err(to_io_error)?;
+        enumerator.match_subsystem("input").map_err(to_io_error)?;
+        enumerator.match_parent(&parent).map_err(to_io_error)?;
+
+        enumerator
+            .scan_devices()
+            .map_err(to_io_error)?
+            .find_map(|device| device.devnode().map(Path::to_path_buf))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no eventN node for device"))
+    }
+
+    /// Resolve `sysname` to the `/dev/input/eventN` path for this device.
+    #[cfg(not(feature = "udev"))]
+    pub fn devnode(&self) -> io::Result<PathBuf> {
+        let sysname = self.sysname()?;
+        let class_dir = PathBuf::from(format!("/sys/class/input/{}", sysname));
+
+        for entry in std::fs::read_dir(&class_dir)? {
+            let name = entry?.file_name();
+
+            if name.to_string_lossy().starts_with("event") {
+                return Ok(PathBuf::from("/dev/input").join(name));
+            }
+        }
+
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "no eventN node under sysfs",
+        ))
+    }
+}
+
+#[cfg(feature = "udev")]
+fn to_io_error<E: std::error::Error + Send + Sync + 'static>(err: E) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, err)
 }
 
 impl Drop for Device {
@@ -0,0 +1,280 @@
+#![allow(non_camel_case_types)]
+
+use libc::{c_char, c_int, timeval};
+use nix::{request_code_none, request_code_read, request_code_readwrite, request_code_write};
+use std::mem;
+
+pub const UINPUT_MAX_NAME_SIZE: u32 = 80;
+pub const ABS_CNT: u32 = 0x40;
+
+pub const EV_SYN: u16 = 0x00;
+pub const EV_KEY: u16 = 0x01;
+pub const EV_REL: u16 = 0x02;
+pub const EV_ABS: u16 = 0x03;
+pub const EV_FF: u16 = 0x15;
+pub const EV_UINPUT: u16 = 0x0101;
+
+pub const SYN_REPORT: u16 = 0;
+
+pub const UI_FF_UPLOAD: u16 = 1;
+pub const UI_FF_ERASE: u16 = 2;
+
+pub const FF_RUMBLE: u16 = 0x50;
+pub const FF_PERIODIC: u16 = 0x51;
+
+pub const ABS_MT_SLOT: u16 = 0x2f;
+pub const ABS_MT_TRACKING_ID: u16 = 0x39;
+pub const ABS_MT_POSITION_X: u16 = 0x35;
+pub const ABS_MT_POSITION_Y: u16 = 0x36;
+
+/// `struct input_id` from `linux/input.h`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct input_id {
+    pub bustype: u16,
+    pub vendor: u16,
+    pub product: u16,
+    pub version: u16,
+}
+
+/// `struct uinput_user_dev` from `linux/uinput.h`, the legacy setup struct.
+#[repr(C)]
+pub struct uinput_user_dev {
+    pub name: [c_char; UINPUT_MAX_NAME_SIZE as usize],
+    pub id: input_id,
+    pub ff_effects_max: u32,
+    pub absmax: [i32; ABS_CNT as usize],
+    pub absmin: [i32; ABS_CNT as usize],
+    pub absfuzz: [i32; ABS_CNT as usize],
+    pub absflat: [i32; ABS_CNT as usize],
+}
+
+/// `struct input_event` from `linux/input.h`.
+#[repr(C)]
+pub struct input_event {
+    pub time: timeval,
+    pub kind: u16,
+    pub code: u16,
+    pub value: i32,
+}
+
+pub unsafe fn ui_dev_create(fd: c_int) -> c_int {
+    libc::ioctl(fd, request_code_none!(b'U', 1) as _)
+}
+
+pub unsafe fn ui_dev_destroy(fd: c_int) -> c_int {
+    libc::ioctl(fd, request_code_none!(b'U', 2) as _)
+}
+
+pub unsafe fn ui_set_evbit(fd: c_int, value: c_int) -> c_int {
+    libc::ioctl(
+        fd,
+        request_code_write!(b'U', 100, mem::size_of::<c_int>()) as _,
+        value,
+    )
+}
+
+pub unsafe fn ui_set_keybit(fd: c_int, value: c_int) -> c_int {
+    libc::ioctl(
+        fd,
+        request_code_write!(b'U', 101, mem::size_of::<c_int>()) as _,
+        value,
+    )
+}
+
+pub unsafe fn ui_set_relbit(fd: c_int, value: c_int) -> c_int {
+    libc::ioctl(
+        fd,
+        request_code_write!(b'U', 102, mem::size_of::<c_int>()) as _,
+        value,
+    )
+}
+
+pub unsafe fn ui_set_absbit(fd: c_int, value: c_int) -> c_int {
+    libc::ioctl(
+        fd,
+        request_code_write!(b'U', 103, mem::size_of::<c_int>()) as _,
+        value,
+    )
+}
+
+pub unsafe fn ui_set_ffbit(fd: c_int, value: c_int) -> c_int {
+    libc::ioctl(
+        fd,
+        request_code_write!(b'U', 107, mem::size_of::<c_int>()) as _,
+        value,
+    )
+}
+
+/// `struct input_absinfo` from `linux/input.h`, describing a single absolute axis.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct input_absinfo {
+    pub value: i32,
+    pub minimum: i32,
+    pub maximum: i32,
+    pub fuzz: i32,
+    pub flat: i32,
+    pub resolution: i32,
+}
+
+/// `struct uinput_setup` from `linux/uinput.h`, the modern (v5) device identity struct.
+#[repr(C)]
+pub struct uinput_setup {
+    pub id: input_id,
+    pub name: [c_char; UINPUT_MAX_NAME_SIZE as usize],
+    pub ff_effects_max: u32,
+}
+
+/// `struct uinput_abs_setup` from `linux/uinput.h`, the modern per-axis setup struct.
+#[repr(C)]
+pub struct uinput_abs_setup {
+    pub code: u16,
+    pub absinfo: input_absinfo,
+}
+
+pub unsafe fn ui_dev_setup(fd: c_int, setup: *const uinput_setup) -> c_int {
+    libc::ioctl(
+        fd,
+        request_code_write!(b'U', 3, mem::size_of::<uinput_setup>()) as _,
+        setup,
+    )
+}
+
+pub unsafe fn ui_abs_setup(fd: c_int, setup: *const uinput_abs_setup) -> c_int {
+    libc::ioctl(
+        fd,
+        request_code_write!(b'U', 4, mem::size_of::<uinput_abs_setup>()) as _,
+        setup,
+    )
+}
+
+/// `struct ff_replay` from `linux/input.h`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct ff_replay {
+    pub length: u16,
+    pub delay: u16,
+}
+
+/// `struct ff_trigger` from `linux/input.h`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct ff_trigger {
+    pub button: u16,
+    pub interval: u16,
+}
+
+/// `struct ff_envelope` from `linux/input.h`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct ff_envelope {
+    pub attack_length: u16,
+    pub attack_level: u16,
+    pub fade_length: u16,
+    pub fade_level: u16,
+}
+
+/// `struct ff_rumble_effect` from `linux/input.h`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct ff_rumble_effect {
+    pub strong_magnitude: u16,
+    pub weak_magnitude: u16,
+}
+
+/// `struct ff_periodic_effect` from `linux/input.h`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct ff_periodic_effect {
+    pub waveform: u16,
+    pub period: u16,
+    pub magnitude: i16,
+    pub offset: i16,
+    pub phase: u16,
+    pub envelope: ff_envelope,
+    pub custom_len: u32,
+    pub custom_data: *mut i16,
+}
+
+/// The `union ff_effect_data` member of `struct ff_effect`. Only the variants
+/// we actually surface to callers are modelled.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub union ff_effect_data {
+    pub rumble: ff_rumble_effect,
+    pub periodic: ff_periodic_effect,
+}
+
+/// `struct ff_effect` from `linux/input.h`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct ff_effect {
+    pub kind: u16,
+    pub id: i16,
+    pub direction: u16,
+    pub trigger: ff_trigger,
+    pub replay: ff_replay,
+    pub u: ff_effect_data,
+}
+
+/// `struct uinput_ff_upload` from `linux/uinput.h`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct uinput_ff_upload {
+    pub request_id: u32,
+    pub retval: i32,
+    pub effect: ff_effect,
+    pub old: ff_effect,
+}
+
+/// `struct uinput_ff_erase` from `linux/uinput.h`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct uinput_ff_erase {
+    pub request_id: u32,
+    pub retval: i32,
+    pub effect_id: u32,
+}
+
+pub unsafe fn ui_begin_ff_upload(fd: c_int, upload: *mut uinput_ff_upload) -> c_int {
+    libc::ioctl(
+        fd,
+        request_code_readwrite!(b'U', 200, mem::size_of::<uinput_ff_upload>()) as _,
+        upload,
+    )
+}
+
+pub unsafe fn ui_end_ff_upload(fd: c_int, upload: *mut uinput_ff_upload) -> c_int {
+    libc::ioctl(
+        fd,
+        request_code_write!(b'U', 201, mem::size_of::<uinput_ff_upload>()) as _,
+        upload,
+    )
+}
+
+pub unsafe fn ui_begin_ff_erase(fd: c_int, erase: *mut uinput_ff_erase) -> c_int {
+    libc::ioctl(
+        fd,
+        request_code_readwrite!(b'U', 202, mem::size_of::<uinput_ff_erase>()) as _,
+        erase,
+    )
+}
+
+pub unsafe fn ui_end_ff_erase(fd: c_int, erase: *mut uinput_ff_erase) -> c_int {
+    libc::ioctl(
+        fd,
+        request_code_write!(b'U', 203, mem::size_of::<uinput_ff_erase>()) as _,
+        erase,
+    )
+}
+
+/// `UI_GET_SYSNAME(len)` from `linux/uinput.h`. The ioctl number is
+/// parameterized on the caller-supplied buffer length.
+pub unsafe fn ui_get_sysname(fd: c_int, buf: &mut [u8]) -> c_int {
+    libc::ioctl(
+        fd,
+        request_code_read!(b'U', 44, buf.len()) as _,
+        buf.as_mut_ptr(),
+    )
+}
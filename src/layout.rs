@@ -0,0 +1,173 @@
+use crate::event::keyboard::Key;
+
+/// A single keystroke needed to produce one character: the base key, and
+/// whether Shift must be held while it's pressed.
+pub struct Step {
+    pub key: Key,
+    pub shift: bool,
+}
+
+/// Maps Unicode scalars to the keystrokes needed to type them on some
+/// physical keyboard layout.
+pub trait Layout {
+    /// Look up the keystroke needed to type `ch`, or `None` if this layout
+    /// has no mapping for it. Each character maps to exactly one `Step`;
+    /// characters that take more than one keystroke cannot be mapped.
+    fn lookup(&self, ch: char) -> Option<Step>;
+}
+
+/// The standard US QWERTY layout.
+pub struct Us;
+
+impl Layout for Us {
+    fn lookup(&self, ch: char) -> Option<Step> {
+        let (key, shift) = match ch {
+            'a'..='z' => (letter(ch.to_ascii_uppercase())?, false),
+            'A'..='Z' => (letter(ch)?, true),
+
+            '0' => (Key::_0, false),
+            '1'..='9' => (digit(ch)?, false),
+
+            ')' => (Key::_0, true),
+            '!' => (Key::_1, true),
+            '@' => (Key::_2, true),
+            '#' => (Key::_3, true),
+            '$' => (Key::_4, true),
+            '%' => (Key::_5, true),
+            '^' => (Key::_6, true),
+            '&' => (Key::_7, true),
+            '*' => (Key::_8, true),
+            '(' => (Key::_9, true),
+
+            '`' => (Key::Grave, false),
+            '~' => (Key::Grave, true),
+            '-' => (Key::Minus, false),
+            '_' => (Key::Minus, true),
+            '=' => (Key::Equal, false),
+            '+' => (Key::Equal, true),
+            '[' => (Key::LeftBrace, false),
+            '{' => (Key::LeftBrace, true),
+            ']' => (Key::RightBrace, false),
+            '}' => (Key::RightBrace, true),
+            ';' => (Key::SemiColon, false),
+            ':' => (Key::SemiColon, true),
+            '\'' => (Key::Apostrophe, false),
+            '"' => (Key::Apostrophe, true),
+            '\\' => (Key::BackSlash, false),
+            '|' => (Key::BackSlash, true),
+            ',' => (Key::Comma, false),
+            '<' => (Key::Comma, true),
+            '.' => (Key::Dot, false),
+            '>' => (Key::Dot, true),
+            '/' => (Key::Slash, false),
+            '?' => (Key::Slash, true),
+
+            ' ' => (Key::Space, false),
+            '\n' => (Key::Enter, false),
+            '\t' => (Key::Tab, false),
+
+            _ => return None,
+        };
+
+        Some(Step { key, shift })
+    }
+}
+
+fn letter(ch: char) -> Option<Key> {
+    Some(match ch {
+        'A' => Key::A,
+        'B' => Key::B,
+        'C' => Key::C,
+        'D' => Key::D,
+        'E' => Key::E,
+        'F' => Key::F,
+        'G' => Key::G,
+        'H' => Key::H,
+        'I' => Key::I,
+        'J' => Key::J,
+        'K' => Key::K,
+        'L' => Key::L,
+        'M' => Key::M,
+        'N' => Key::N,
+        'O' => Key::O,
+        'P' => Key::P,
+        'Q' => Key::Q,
+        'R' => Key::R,
+        'S' => Key::S,
+        'T' => Key::T,
+        'U' => Key::U,
+        'V' => Key::V,
+        'W' => Key::W,
+        'X' => Key::X,
+        'Y' => Key::Y,
+        'Z' => Key::Z,
+        _ => return None,
+    })
+}
+
+fn digit(ch: char) -> Option<Key> {
+    Some(match ch {
+        '1' => Key::_1,
+        '2' => Key::_2,
+        '3' => Key::_3,
+        '4' => Key::_4,
+        '5' => Key::_5,
+        '6' => Key::_6,
+        '7' => Key::_7,
+        '8' => Key::_8,
+        '9' => Key::_9,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lowercase_letter_has_no_shift() {
+        let step = Us.lookup('h').unwrap();
+        assert!(matches!(step.key, Key::H));
+        assert!(!step.shift);
+    }
+
+    #[test]
+    fn uppercase_letter_requires_shift() {
+        let step = Us.lookup('H').unwrap();
+        assert!(matches!(step.key, Key::H));
+        assert!(step.shift);
+    }
+
+    #[test]
+    fn digit_has_no_shift() {
+        let step = Us.lookup('7').unwrap();
+        assert!(matches!(step.key, Key::_7));
+        assert!(!step.shift);
+    }
+
+    #[test]
+    fn shifted_symbol_over_digit_row() {
+        let step = Us.lookup('!').unwrap();
+        assert!(matches!(step.key, Key::_1));
+        assert!(step.shift);
+    }
+
+    #[test]
+    fn punctuation_without_shift() {
+        let step = Us.lookup(',').unwrap();
+        assert!(matches!(step.key, Key::Comma));
+        assert!(!step.shift);
+    }
+
+    #[test]
+    fn shifted_punctuation() {
+        let step = Us.lookup('?').unwrap();
+        assert!(matches!(step.key, Key::Slash));
+        assert!(step.shift);
+    }
+
+    #[test]
+    fn unmapped_character_returns_none() {
+        assert!(Us.lookup('\u{1f600}').is_none());
+    }
+}
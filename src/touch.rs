@@ -0,0 +1,153 @@
+use ffi::{ABS_MT_POSITION_X, ABS_MT_POSITION_Y, ABS_MT_SLOT, ABS_MT_TRACKING_ID, EV_ABS};
+
+use crate::Device;
+
+/// Helper implementing the kernel's multitouch type-B protocol
+/// (`ABS_MT_SLOT` + `ABS_MT_TRACKING_ID`) on top of a `Device` built with
+/// `Builder::multi_touch`, which enables `ABS_MT_SLOT`, `ABS_MT_TRACKING_ID`,
+/// `ABS_MT_POSITION_X` and `ABS_MT_POSITION_Y`.
+///
+/// Tracking ids must be unique and monotonically assigned while a contact
+/// is live; `begin_contact` hands back the id it assigned. The caller is
+/// responsible for calling `Device::synchronize` once per frame after
+/// issuing all of that frame's `begin_contact`/`move_contact`/`end_contact`
+/// calls, so a gesture is never observed half-applied.
+pub struct MultiTouch<'a> {
+    device: &'a mut Device,
+    next_id: i32,
+    active_slot: Option<i32>,
+}
+
+impl<'a> MultiTouch<'a> {
+    /// Wrap `device`, which must have been built with `Builder::multi_touch`.
+    pub fn new(device: &'a mut Device) -> Self {
+        MultiTouch {
+            device,
+            next_id: 0,
+            active_slot: None,
+        }
+    }
+
+    /// Begin a new contact in `slot` at `(x, y)`, returning the tracking id
+    /// assigned to it.
+    pub fn begin_contact(
+        &mut self,
+        slot: i32,
+        x: i32,
+        y: i32,
+    ) -> Result<i32, Box<dyn std::error::Error>> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        self.select_slot(slot)?;
+        self.device
+            .write(EV_ABS as _, ABS_MT_TRACKING_ID as _, id)?;
+        self.device.write(EV_ABS as _, ABS_MT_POSITION_X as _, x)?;
+        self.device.write(EV_ABS as _, ABS_MT_POSITION_Y as _, y)?;
+
+        Ok(id)
+    }
+
+    /// Move the already-live contact in `slot` to `(x, y)`.
+    pub fn move_contact(
+        &mut self,
+        slot: i32,
+        x: i32,
+        y: i32,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.select_slot(slot)?;
+        self.device.write(EV_ABS as _, ABS_MT_POSITION_X as _, x)?;
+        self.device.write(EV_ABS as _, ABS_MT_POSITION_Y as _, y)?;
+
+        Ok(())
+    }
+
+    /// End the contact in `slot`, lifting it.
+    pub fn end_contact(&mut self, slot: i32) -> Result<(), Box<dyn std::error::Error>> {
+        self.select_slot(slot)?;
+        self.device
+            .write(EV_ABS as _, ABS_MT_TRACKING_ID as _, -1)?;
+
+        Ok(())
+    }
+
+    fn select_slot(&mut self, slot: i32) -> Result<(), Box<dyn std::error::Error>> {
+        if self.active_slot != Some(slot) {
+            self.device.write(EV_ABS as _, ABS_MT_SLOT as _, slot)?;
+            self.active_slot = Some(slot);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nix::{fcntl, unistd};
+    use std::mem;
+
+    /// Reads every pending `input_event` off `fd` and returns the
+    /// `(kind, code, value)` triples, for asserting on exactly what a
+    /// `MultiTouch` call wrote.
+    fn drain_events(fd: std::os::unix::io::RawFd) -> Vec<(u16, u16, i32)> {
+        let size = mem::size_of::<ffi::input_event>();
+        let mut events = Vec::new();
+
+        fcntl::fcntl(fd, fcntl::FcntlArg::F_SETFL(fcntl::OFlag::O_NONBLOCK)).unwrap();
+
+        loop {
+            let mut event: ffi::input_event = unsafe { mem::zeroed() };
+            let buf = unsafe {
+                std::slice::from_raw_parts_mut(&mut event as *mut _ as *mut u8, size)
+            };
+
+            match unistd::read(fd, buf) {
+                Ok(read) if read == size => events.push((event.kind, event.code, event.value)),
+                _ => break,
+            }
+        }
+
+        events
+    }
+
+    #[test]
+    fn select_slot_skips_redundant_write_for_same_slot() {
+        let (read_fd, write_fd) = unistd::pipe().unwrap();
+        let mut device = Device::new(write_fd);
+        let mut touch = MultiTouch::new(&mut device);
+
+        touch.select_slot(0).unwrap();
+        touch.select_slot(0).unwrap();
+
+        let events = drain_events(read_fd);
+        let slot_writes = events
+            .iter()
+            .filter(|&&(kind, code, _)| kind == EV_ABS && code == ABS_MT_SLOT)
+            .count();
+
+        assert_eq!(slot_writes, 1);
+
+        unistd::close(read_fd).ok();
+    }
+
+    #[test]
+    fn select_slot_writes_again_for_a_different_slot() {
+        let (read_fd, write_fd) = unistd::pipe().unwrap();
+        let mut device = Device::new(write_fd);
+        let mut touch = MultiTouch::new(&mut device);
+
+        touch.select_slot(0).unwrap();
+        touch.select_slot(1).unwrap();
+
+        let events = drain_events(read_fd);
+        let slot_writes = events
+            .iter()
+            .filter(|&&(kind, code, _)| kind == EV_ABS && code == ABS_MT_SLOT)
+            .count();
+
+        assert_eq!(slot_writes, 2);
+
+        unistd::close(read_fd).ok();
+    }
+}